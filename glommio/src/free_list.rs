@@ -11,6 +11,7 @@ use std::fmt;
 #[derive(Debug)]
 pub(crate) struct Idx<T> {
     raw: usize,
+    generation: u32,
     _ty: PhantomData<fn() -> T>,
 }
 
@@ -23,7 +24,7 @@ impl<T: fmt::Debug> Clone for Idx<T> {
 
 impl<T: fmt::Debug> PartialEq for Idx<T> {
     fn eq(&self, other: &Idx<T>) -> bool {
-        self.raw == other.raw
+        self.raw == other.raw && self.generation == other.generation
     }
 }
 impl<T: fmt::Debug> Eq for Idx<T> {}
@@ -32,6 +33,7 @@ impl<T: fmt::Debug> Idx<T> {
     pub(crate) fn from_raw(raw: usize) -> Idx<T> {
         Idx {
             raw,
+            generation: 0,
             _ty: PhantomData,
         }
     }
@@ -44,6 +46,21 @@ impl<T: fmt::Debug> Idx<T> {
 pub(crate) struct FreeList<T: fmt::Debug> {
     first_free: Option<Idx<T>>,
     slots: Vec<Slot<T>>,
+    // `Some(n)` caps the backing storage at `n` slots, turning the list into a
+    // fixed-size pool; `None` lets `alloc` grow without bound.
+    cap: Option<usize>,
+    live: usize,
+    peak_live: usize,
+}
+
+/// A cheap snapshot of a [`FreeList`]'s occupancy, for debug logging and leak
+/// auditing of the long-lived resource tables these lists back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FreeListStats {
+    pub(crate) live: usize,
+    pub(crate) free: usize,
+    pub(crate) capacity: usize,
+    pub(crate) peak_live: usize,
 }
 
 impl<T: fmt::Debug> Default for FreeList<T> {
@@ -51,66 +68,548 @@ impl<T: fmt::Debug> Default for FreeList<T> {
         FreeList {
             first_free: None,
             slots: Vec::new(),
+            cap: None,
+            live: 0,
+            peak_live: 0,
         }
     }
 }
 
 impl<T: fmt::Debug> FreeList<T> {
+    /// Creates a fixed-capacity list preallocating `n` free slots. `alloc` will
+    /// hand them out without ever growing past `n`, so pools backing io_uring
+    /// submission slots and similar can apply backpressure instead of ballooning.
+    pub(crate) fn with_capacity(n: usize) -> Self {
+        let mut slots = Vec::with_capacity(n);
+        for i in 0..n {
+            let next_free = if i + 1 < n {
+                Some(Idx::from_raw(i + 1))
+            } else {
+                None
+            };
+            slots.push(Slot {
+                generation: 0,
+                entry: Entry::Free { next_free, run: 0 },
+            });
+        }
+        let first_free = if n > 0 { Some(Idx::from_raw(0)) } else { None };
+        let mut list = FreeList {
+            first_free,
+            slots,
+            cap: Some(n),
+            live: 0,
+            peak_live: 0,
+        };
+        if n > 0 {
+            list.set_run(0, n - 1);
+        }
+        list
+    }
+
+    /// Allocates `item`, growing the backing storage when the free chain is
+    /// empty. A fixed-capacity list cannot grow past its cap, so an infallible `alloc` on a
+    /// full bounded pool has no way to report exhaustion and panics; callers of
+    /// bounded lists should gate on `is_full`/`try_alloc` to apply backpressure.
     pub(crate) fn alloc(&mut self, item: T) -> Idx<T> {
-        let slot = Slot::Full { item };
+        match self.reuse(item) {
+            Ok(idx) => idx,
+            Err(item) => match self.cap {
+                // A fixed-capacity list never grows past its cap: reaching this arm means
+                // the pool is exhausted. Infallible `alloc` cannot report that,
+                // so callers of bounded lists should gate on `is_full`/`try_alloc`.
+                Some(cap) if self.slots.len() >= cap => {
+                    panic!("FreeList reached its fixed capacity of {}; use try_alloc for backpressure", cap);
+                }
+                _ => self.push(item),
+            },
+        }
+    }
+
+    /// Allocates only into an existing free slot, returning the item untouched
+    /// when none is available rather than growing the backing storage.
+    pub(crate) fn try_alloc(&mut self, item: T) -> Result<Idx<T>, T> {
+        self.reuse(item)
+    }
+
+    /// Number of live entries.
+    pub(crate) fn len(&self) -> usize {
+        self.live
+    }
+
+    /// Maximum number of entries the list can hold: the configured cap for a
+    /// fixed-capacity list, or the current backing size for a growable one.
+    pub(crate) fn capacity(&self) -> usize {
+        self.cap.unwrap_or(self.slots.len())
+    }
+
+    /// Whether a fixed-capacity list can no longer accept a new entry. A
+    /// growable list is never full.
+    pub(crate) fn is_full(&self) -> bool {
+        match self.cap {
+            Some(cap) => self.first_free.is_none() && self.slots.len() >= cap,
+            None => false,
+        }
+    }
+
+    // Fills the head of the free chain with `item`, or hands `item` back when the
+    // chain is empty. Shared by `alloc` and `try_alloc`.
+    fn reuse(&mut self, item: T) -> Result<Idx<T>, T> {
         match self.first_free {
             Some(idx) => {
-                self.first_free = match mem::replace(&mut self.slots[idx.to_raw()], slot) {
-                    Slot::Free { next_free } => next_free,
-                    Slot::Full { .. } => {
+                let slot = &mut self.slots[idx.to_raw()];
+                let generation = slot.generation;
+                // Grab the run length off the slot before it is overwritten. It is
+                // authoritative when the slot is an endpoint of its free run, so
+                // `split_run` can then fix the remaining endpoint without walking.
+                let run = match mem::replace(&mut slot.entry, Entry::Full { item }) {
+                    Entry::Free { next_free, run } => {
+                        self.first_free = next_free;
+                        run
+                    }
+                    Entry::Full { .. } => {
                         panic!("id {:?} was full already. First free: {:?} Status: {:?}", idx,  self.first_free, self.slots);
                     }
                 };
-                idx
-            }
-            None => {
-                let idx = Idx::from_raw(self.slots.len());
-                self.slots.push(slot);
-                idx
+                self.split_run(idx.to_raw(), run);
+                self.live += 1;
+                self.peak_live = self.peak_live.max(self.live);
+                Ok(Idx {
+                    raw: idx.to_raw(),
+                    generation,
+                    _ty: PhantomData,
+                })
             }
+            None => Err(item),
+        }
+    }
+
+    // Appends a fresh slot, growing the backing storage.
+    fn push(&mut self, item: T) -> Idx<T> {
+        let idx = Idx::from_raw(self.slots.len());
+        self.slots.push(Slot {
+            generation: 0,
+            entry: Entry::Full { item },
+        });
+        self.live += 1;
+        self.peak_live = self.peak_live.max(self.live);
+        idx
+    }
+
+    /// Returns a cheap occupancy snapshot: live entries, free slots, total
+    /// capacity, and the high-water mark of live entries since construction.
+    pub(crate) fn stats(&self) -> FreeListStats {
+        FreeListStats {
+            live: self.live,
+            free: self.slots.len() - self.live,
+            capacity: self.capacity(),
+            peak_live: self.peak_live,
         }
     }
+
+    /// Visits every still-occupied entry as `(Idx<T>, &T)`, so a debug build can
+    /// log what was never deallocated — "N sources leaked" — at shutdown.
+    pub(crate) fn audit(&self) -> Iter<'_, T> {
+        self.iter()
+    }
     pub(crate) fn dealloc(&mut self, idx: Idx<T>) -> T {
-        let slot = Slot::Free {
-            next_free: mem::replace(&mut self.first_free, Some(idx)),
-        };
-        match mem::replace(&mut self.slots[idx.to_raw()], slot) {
-            Slot::Full { item } => item,
-            Slot::Free { .. } => {
+        let prev_first_free = self.first_free;
+        let slot = &mut self.slots[idx.to_raw()];
+        let removed = mem::replace(
+            &mut slot.entry,
+            Entry::Free {
+                next_free: prev_first_free,
+                run: 1,
+            },
+        );
+        // Bump the generation so that every handle still pointing at this slot
+        // (including the one we were just handed) is now stale. Wrapping at
+        // u32::MAX reopens a narrow ABA window where a handle from exactly
+        // 2^32 reuses ago would validate again; in practice that is unreachable.
+        slot.generation = slot.generation.wrapping_add(1);
+        self.first_free = Some(Idx {
+            raw: idx.to_raw(),
+            generation: slot.generation,
+            _ty: PhantomData,
+        });
+        self.coalesce_run(idx.to_raw());
+        match removed {
+            Entry::Full { item } => {
+                self.live -= 1;
+                item
+            }
+            Entry::Free { .. } => {
                 panic!("id {:?} was empty already, First free {:?}, Status: {:?}", idx,  self.first_free, self.slots);
             }
         }
     }
+
+    /// Returns the item behind `idx`, or `None` if the handle is stale: the slot
+    /// was freed (and possibly reused) since `idx` was issued, or the index is
+    /// out of range.
+    pub(crate) fn get(&self, idx: Idx<T>) -> Option<&T> {
+        let slot = self.slots.get(idx.to_raw())?;
+        if slot.generation != idx.generation {
+            return None;
+        }
+        match &slot.entry {
+            Entry::Full { item } => Some(item),
+            Entry::Free { .. } => None,
+        }
+    }
+
+    pub(crate) fn get_mut(&mut self, idx: Idx<T>) -> Option<&mut T> {
+        let slot = self.slots.get_mut(idx.to_raw())?;
+        if slot.generation != idx.generation {
+            return None;
+        }
+        match &mut slot.entry {
+            Entry::Full { item } => Some(item),
+            Entry::Free { .. } => None,
+        }
+    }
+
+    /// Walks every live entry in index order, visiting `Entry::Full` slots only.
+    ///
+    /// Runs of freed slots are skipped in a single jump by reading the leading
+    /// skip count recorded at the start of the run, so the cost is proportional
+    /// to the number of occupied slots rather than the capacity.
+    pub(crate) fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            slots: &self.slots,
+            cursor: 0,
+        }
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            rest: &mut self.slots,
+            base: 0,
+        }
+    }
+
+    /// Consumes every live entry, leaving the list empty. The configured
+    /// capacity and the peak-live high-water mark are preserved, and every
+    /// slot's generation is carried forward (bumped for the entries that were
+    /// still live), so handles issued before the drain stay stale just as after
+    /// `dealloc`.
+    pub(crate) fn drain(&mut self) -> Drain<T> {
+        // Lift the entries out for the caller, keeping the backing slots in
+        // place so their generation counters survive. The drained copy retains
+        // the original skip counts so the iterator still jumps free runs in one
+        // hop; the slots left behind are reset to single-slot free entries and
+        // re-threaded below.
+        let mut entries = Vec::with_capacity(self.slots.len());
+        for slot in &mut self.slots {
+            let was_live = matches!(slot.entry, Entry::Full { .. });
+            let entry = mem::replace(
+                &mut slot.entry,
+                Entry::Free {
+                    next_free: None,
+                    run: 1,
+                },
+            );
+            if was_live {
+                slot.generation = slot.generation.wrapping_add(1);
+            }
+            entries.push(entry);
+        }
+        self.live = 0;
+        self.rethread_free();
+        Drain { entries, cursor: 0 }
+    }
+
+    /// Reserves backing storage for at least `additional` more entries, so a
+    /// caller expecting a batch can avoid the incremental reallocations `alloc`
+    /// would otherwise perform.
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.slots.reserve(additional);
+    }
+
+    /// Drops the trailing contiguous block of freed slots from the end of the
+    /// backing storage and re-threads the free list over what remains, so no
+    /// surviving `next_free` points past the new length.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        let mut len = self.slots.len();
+        while len > 0 && matches!(self.slots[len - 1].entry, Entry::Free { .. }) {
+            len -= 1;
+        }
+        self.slots.truncate(len);
+        self.slots.shrink_to_fit();
+        // Truncation can have severed a free run and orphaned `next_free`
+        // pointers, so rebuild the chain and the skipfield from scratch over the
+        // retained slots.
+        self.rethread_free();
+    }
+
+    /// Drops every live entry and resets the list to its empty state, keeping
+    /// the configured capacity and the peak-live high-water mark. Every slot's
+    /// generation is carried forward and bumped for the entries that were still
+    /// live, so outstanding handles stay stale exactly as after `dealloc` —
+    /// they never resolve against whatever later reuses the slot.
+    pub(crate) fn clear(&mut self) {
+        for slot in &mut self.slots {
+            if matches!(slot.entry, Entry::Full { .. }) {
+                // Bump so every handle to this still-live slot goes stale,
+                // mirroring `dealloc`.
+                slot.generation = slot.generation.wrapping_add(1);
+                slot.entry = Entry::Free {
+                    next_free: None,
+                    run: 1,
+                };
+            }
+        }
+        self.live = 0;
+        self.rethread_free();
+    }
+
+    // Rebuilds the free chain and every free run's skip counts from the current
+    // slot contents. Free slots are linked in ascending order, so `first_free`
+    // becomes the lowest free index.
+    fn rethread_free(&mut self) {
+        self.first_free = None;
+        for i in (0..self.slots.len()).rev() {
+            if matches!(self.slots[i].entry, Entry::Free { .. }) {
+                let next_free = self.first_free;
+                let generation = self.slots[i].generation;
+                if let Entry::Free { next_free: nf, .. } = &mut self.slots[i].entry {
+                    *nf = next_free;
+                }
+                self.first_free = Some(Idx {
+                    raw: i,
+                    generation,
+                    _ty: PhantomData,
+                });
+            }
+        }
+        let mut i = 0;
+        while i < self.slots.len() {
+            if matches!(self.slots[i].entry, Entry::Free { .. }) {
+                let start = i;
+                while i < self.slots.len() && matches!(self.slots[i].entry, Entry::Free { .. }) {
+                    i += 1;
+                }
+                self.set_run(start, i - 1);
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    // Skipfield maintenance. Each `Entry::Free` records the length of the
+    // contiguous free run it belongs to; the count is authoritative at the
+    // first and last slot of the run (the two endpoints), and `iter` relies on
+    // the leading endpoint to jump a whole run at once.
+
+    fn set_run(&mut self, start: usize, end: usize) {
+        let len = end - start + 1;
+        if let Entry::Free { run, .. } = &mut self.slots[start].entry {
+            *run = len;
+        }
+        if let Entry::Free { run, .. } = &mut self.slots[end].entry {
+            *run = len;
+        }
+    }
+
+    // `at` was just turned into `Free`. Coalesce it with the run ending at
+    // `at - 1` and the run starting at `at + 1` by reading their boundary
+    // counts, then rewrite the two endpoints of the merged run.
+    fn coalesce_run(&mut self, at: usize) {
+        let mut start = at;
+        let mut end = at;
+        if at > 0 {
+            if let Entry::Free { run, .. } = self.slots[at - 1].entry {
+                start = (at - 1) + 1 - run;
+            }
+        }
+        if at + 1 < self.slots.len() {
+            if let Entry::Free { run, .. } = self.slots[at + 1].entry {
+                end = (at + 1) + run - 1;
+            }
+        }
+        self.set_run(start, end);
+    }
+
+    // `at` was just turned into `Full`, splitting the run of length `run` it sat
+    // in. `run` is the count read off `at` before it was overwritten, so it is
+    // authoritative whenever `at` was an endpoint of that run. Rewrite the
+    // endpoints of whatever free slots remain on either side of it.
+    fn split_run(&mut self, at: usize, run: usize) {
+        let left_free = at > 0 && matches!(self.slots[at - 1].entry, Entry::Free { .. });
+        let right_free =
+            at + 1 < self.slots.len() && matches!(self.slots[at + 1].entry, Entry::Free { .. });
+        match (left_free, right_free) {
+            // `at` was an isolated run; nothing is left to re-index.
+            (false, false) => {}
+            // `at` was the left endpoint: the run spanned `[at, at + run - 1]`,
+            // so the surviving right part is `[at + 1, at + run - 1]`. O(1).
+            (false, true) => self.set_run(at + 1, at + run - 1),
+            // `at` was the right endpoint: the run spanned `[at + 1 - run, at]`,
+            // so the surviving left part is `[at + 1 - run, at - 1]`. O(1).
+            (true, false) => self.set_run(at + 1 - run, at - 1),
+            // `at` was interior, so the count read off it was stale; recover the
+            // two surviving endpoints by scanning out from the split point.
+            (true, true) => {
+                let start = self.run_start(at - 1);
+                let end = self.run_end(at + 1);
+                self.set_run(start, at - 1);
+                self.set_run(at + 1, end);
+            }
+        }
+    }
+
+    fn run_start(&self, mut at: usize) -> usize {
+        while at > 0 && matches!(self.slots[at - 1].entry, Entry::Free { .. }) {
+            at -= 1;
+        }
+        at
+    }
+
+    fn run_end(&self, mut at: usize) -> usize {
+        while at + 1 < self.slots.len() && matches!(self.slots[at + 1].entry, Entry::Free { .. }) {
+            at += 1;
+        }
+        at
+    }
 }
 
 impl<T: fmt::Debug> ops::Index<Idx<T>> for FreeList<T> {
     type Output = T;
 
     fn index(&self, idx: Idx<T>) -> &T {
-        match &self.slots[idx.to_raw()] {
-            Slot::Free { .. } => unreachable!(),
-            Slot::Full { item } => item,
-        }
+        self.get(idx)
+            .expect("indexed a FreeList with a stale or freed Idx")
     }
 }
 
 impl<T: fmt::Debug> ops::IndexMut<Idx<T>> for FreeList<T> {
     fn index_mut(&mut self, idx: Idx<T>) -> &mut T {
-        match &mut self.slots[idx.to_raw()] {
-            Slot::Free { .. } => unreachable!(),
-            Slot::Full { item } => item,
+        self.get_mut(idx)
+            .expect("indexed a FreeList with a stale or freed Idx")
+    }
+}
+
+pub(crate) struct Iter<'a, T: fmt::Debug> {
+    slots: &'a [Slot<T>],
+    cursor: usize,
+}
+
+impl<'a, T: fmt::Debug> Iterator for Iter<'a, T> {
+    type Item = (Idx<T>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cursor < self.slots.len() {
+            let i = self.cursor;
+            let generation = self.slots[i].generation;
+            match &self.slots[i].entry {
+                Entry::Full { item } => {
+                    self.cursor += 1;
+                    return Some((
+                        Idx {
+                            raw: i,
+                            generation,
+                            _ty: PhantomData,
+                        },
+                        item,
+                    ));
+                }
+                Entry::Free { run, .. } => self.cursor += run,
+            }
+        }
+        None
+    }
+}
+
+pub(crate) struct IterMut<'a, T: fmt::Debug> {
+    rest: &'a mut [Slot<T>],
+    base: usize,
+}
+
+impl<'a, T: fmt::Debug> Iterator for IterMut<'a, T> {
+    type Item = (Idx<T>, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = mem::take(&mut self.rest);
+            match rest.first() {
+                None => return None,
+                Some(slot) => match &slot.entry {
+                    Entry::Full { .. } => {
+                        let (head, tail) = rest.split_at_mut(1);
+                        self.rest = tail;
+                        let i = self.base;
+                        self.base += 1;
+                        let slot = &mut head[0];
+                        let generation = slot.generation;
+                        match &mut slot.entry {
+                            Entry::Full { item } => {
+                                return Some((
+                                    Idx {
+                                        raw: i,
+                                        generation,
+                                        _ty: PhantomData,
+                                    },
+                                    item,
+                                ))
+                            }
+                            Entry::Free { .. } => unreachable!(),
+                        }
+                    }
+                    Entry::Free { run, .. } => {
+                        let run = *run;
+                        let (_, tail) = rest.split_at_mut(run);
+                        self.rest = tail;
+                        self.base += run;
+                    }
+                },
+            }
+        }
+    }
+}
+
+pub(crate) struct Drain<T: fmt::Debug> {
+    entries: Vec<Entry<T>>,
+    cursor: usize,
+}
+
+impl<T: fmt::Debug> Iterator for Drain<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cursor < self.entries.len() {
+            let i = self.cursor;
+            let run = match &self.entries[i] {
+                Entry::Free { run, .. } => Some(*run),
+                Entry::Full { .. } => None,
+            };
+            match run {
+                Some(run) => self.cursor += run,
+                None => {
+                    self.cursor += 1;
+                    if let Entry::Full { item } = mem::replace(
+                        &mut self.entries[i],
+                        Entry::Free {
+                            next_free: None,
+                            run: 1,
+                        },
+                    ) {
+                        return Some(item);
+                    }
+                }
+            }
         }
+        None
     }
 }
 
 #[derive(Debug)]
-enum Slot<T: fmt::Debug> {
-    Free { next_free: Option<Idx<T>> },
+struct Slot<T: fmt::Debug> {
+    generation: u32,
+    entry: Entry<T>,
+}
+
+#[derive(Debug)]
+enum Entry<T: fmt::Debug> {
+    Free { next_free: Option<Idx<T>>, run: usize },
     Full { item: T },
 }
 
@@ -119,10 +618,10 @@ fn free_list_smoke_test() {
     let mut free_list: FreeList<&str> = FreeList::default();
 
     let hello = free_list.alloc("hello");
-    assert_eq!(hello, Idx::from_raw(0));
+    assert_eq!(hello.to_raw(), 0);
 
     let world = free_list.alloc("world");
-    assert_eq!(world, Idx::from_raw(1));
+    assert_eq!(world.to_raw(), 1);
 
     assert_eq!(free_list[hello], "hello");
     assert_eq!(free_list[world], "world");
@@ -130,7 +629,7 @@ fn free_list_smoke_test() {
     free_list.dealloc(hello);
 
     let goodbye = free_list.alloc("goodbye");
-    assert_eq!(goodbye, Idx::from_raw(0));
+    assert_eq!(goodbye.to_raw(), 0);
 
     free_list.dealloc(goodbye);
     free_list.dealloc(world);
@@ -138,7 +637,215 @@ fn free_list_smoke_test() {
     let a = free_list.alloc("a");
     let b = free_list.alloc("b");
     let c = free_list.alloc("c");
-    assert_eq!(a, Idx::from_raw(1));
-    assert_eq!(b, Idx::from_raw(0));
-    assert_eq!(c, Idx::from_raw(2));
+    assert_eq!(a.to_raw(), 1);
+    assert_eq!(b.to_raw(), 0);
+    assert_eq!(c.to_raw(), 2);
+}
+
+#[test]
+fn stale_handles_return_none() {
+    let mut free_list: FreeList<&str> = FreeList::default();
+
+    let hello = free_list.alloc("hello");
+    assert_eq!(free_list.get(hello), Some(&"hello"));
+
+    free_list.dealloc(hello);
+    // The freed handle no longer resolves, even though the slot still exists.
+    assert_eq!(free_list.get(hello), None);
+
+    // Reusing the slot hands out a fresh generation; the old handle stays stale
+    // while the new one resolves.
+    let world = free_list.alloc("world");
+    assert_eq!(hello.to_raw(), world.to_raw());
+    assert_eq!(free_list.get(hello), None);
+    assert_eq!(free_list.get(world), Some(&"world"));
+}
+
+#[test]
+fn iter_visits_only_live_entries() {
+    let mut free_list: FreeList<u32> = FreeList::default();
+    let mut keys = Vec::new();
+    for i in 0..8 {
+        keys.push(free_list.alloc(i));
+    }
+    // Punch a sparse set of holes, creating both isolated and adjacent runs.
+    free_list.dealloc(keys[1]);
+    free_list.dealloc(keys[2]);
+    free_list.dealloc(keys[3]);
+    free_list.dealloc(keys[6]);
+
+    let live: Vec<u32> = free_list.iter().map(|(_, v)| *v).collect();
+    assert_eq!(live, vec![0, 4, 5, 7]);
+
+    for (_, v) in free_list.iter_mut() {
+        *v += 100;
+    }
+    let live: Vec<u32> = free_list.iter().map(|(_, v)| *v).collect();
+    assert_eq!(live, vec![100, 104, 105, 107]);
+
+    let drained: Vec<u32> = free_list.drain().collect();
+    assert_eq!(drained, vec![100, 104, 105, 107]);
+    assert_eq!(free_list.iter().count(), 0);
+}
+
+#[test]
+fn fixed_capacity_applies_backpressure() {
+    let mut free_list: FreeList<u32> = FreeList::with_capacity(2);
+    assert_eq!(free_list.capacity(), 2);
+    assert_eq!(free_list.len(), 0);
+    assert!(!free_list.is_full());
+
+    let a = free_list.try_alloc(1).unwrap();
+    let _b = free_list.try_alloc(2).unwrap();
+    assert_eq!(free_list.len(), 2);
+    assert!(free_list.is_full());
+
+    // No free slot left: the item comes straight back instead of growing.
+    assert_eq!(free_list.try_alloc(3), Err(3));
+    assert_eq!(free_list.capacity(), 2);
+
+    // Freeing one re-opens a slot.
+    free_list.dealloc(a);
+    assert!(!free_list.is_full());
+    assert_eq!(free_list.len(), 1);
+    let c = free_list.try_alloc(3).unwrap();
+    assert_eq!(free_list[c], 3);
+}
+
+#[test]
+fn shrink_to_fit_rethreads_free_list() {
+    let mut free_list: FreeList<u32> = FreeList::default();
+    free_list.reserve(6);
+    let keys: Vec<_> = (0..6).map(|i| free_list.alloc(i)).collect();
+
+    // Free the tail plus an interior hole, so shrinking must both truncate and
+    // re-thread the hole, whose `next_free` points into the truncated tail.
+    free_list.dealloc(keys[5]);
+    free_list.dealloc(keys[4]);
+    free_list.dealloc(keys[2]);
+
+    free_list.shrink_to_fit();
+    assert_eq!(free_list.capacity(), 4);
+    free_runs_consistent(&free_list);
+
+    // The surviving interior hole is still reusable and the live set is intact.
+    let reused = free_list.alloc(20);
+    assert_eq!(reused.to_raw(), 2);
+    let mut live: Vec<u32> = free_list.iter().map(|(_, v)| *v).collect();
+    live.sort_unstable();
+    assert_eq!(live, vec![0, 1, 3, 20]);
+
+    free_list.clear();
+    assert_eq!(free_list.len(), 0);
+    assert_eq!(free_list.iter().count(), 0);
+}
+
+#[test]
+fn clear_leaves_handles_stale() {
+    // A handle to an entry that is never deallocated must not survive a clear:
+    // after the slot is reused the old handle has to resolve to `None` rather
+    // than alias the new occupant.
+    let mut free_list: FreeList<&str> = FreeList::default();
+    let old = free_list.alloc("old");
+    free_list.clear();
+    let _new = free_list.alloc("new");
+    assert_eq!(free_list.get(old), None);
+}
+
+#[test]
+fn drain_leaves_handles_stale() {
+    // Same guarantee across a drain: the pre-drain handle must not resolve to
+    // whatever later reuses the slot.
+    let mut free_list: FreeList<&str> = FreeList::default();
+    let old = free_list.alloc("old");
+    let drained: Vec<&str> = free_list.drain().collect();
+    assert_eq!(drained, vec!["old"]);
+    let _new = free_list.alloc("new");
+    assert_eq!(free_list.get(old), None);
+}
+
+#[test]
+fn stats_and_audit_track_occupancy() {
+    let mut free_list: FreeList<u32> = FreeList::default();
+    let keys: Vec<_> = (0..5).map(|i| free_list.alloc(i)).collect();
+    assert_eq!(
+        free_list.stats(),
+        FreeListStats {
+            live: 5,
+            free: 0,
+            capacity: 5,
+            peak_live: 5,
+        }
+    );
+
+    free_list.dealloc(keys[1]);
+    free_list.dealloc(keys[3]);
+    let stats = free_list.stats();
+    assert_eq!(stats.live, 3);
+    assert_eq!(stats.free, 2);
+    // The high-water mark stays at the peak even after entries are freed.
+    assert_eq!(stats.peak_live, 5);
+
+    // `audit` surfaces exactly the entries still holding a resource.
+    let mut leaked: Vec<(usize, u32)> = free_list.audit().map(|(idx, v)| (idx.to_raw(), *v)).collect();
+    leaked.sort_unstable();
+    assert_eq!(leaked, vec![(0, 0), (2, 2), (4, 4)]);
+}
+
+#[cfg(test)]
+fn free_runs_consistent<T: fmt::Debug>(list: &FreeList<T>) {
+    let mut i = 0;
+    while i < list.slots.len() {
+        match &list.slots[i].entry {
+            Entry::Full { .. } => i += 1,
+            Entry::Free { .. } => {
+                let start = i;
+                while i < list.slots.len() && matches!(list.slots[i].entry, Entry::Free { .. }) {
+                    i += 1;
+                }
+                let end = i - 1;
+                let len = end - start + 1;
+                for &endpoint in &[start, end] {
+                    if let Entry::Free { run, .. } = &list.slots[endpoint].entry {
+                        assert_eq!(*run, len, "endpoint count disagrees for run [{}, {}]", start, end);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn skipfield_endpoints_stay_consistent() {
+    // A small linear-congruential generator keeps the sequence deterministic
+    // without reaching for the clock or the rng crate.
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut next = || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 33) as usize
+    };
+
+    let mut free_list: FreeList<usize> = FreeList::default();
+    let mut live: Vec<(Idx<usize>, usize)> = Vec::new();
+    let mut counter = 0usize;
+
+    for _ in 0..5000 {
+        if live.is_empty() || next() % 2 == 0 {
+            let value = counter;
+            counter += 1;
+            let idx = free_list.alloc(value);
+            live.push((idx, value));
+        } else {
+            let pos = next() % live.len();
+            let (idx, value) = live.swap_remove(pos);
+            assert_eq!(free_list.dealloc(idx), value);
+        }
+        free_runs_consistent(&free_list);
+    }
+
+    let mut expected: Vec<usize> = live.iter().map(|(_, v)| *v).collect();
+    expected.sort_unstable();
+    let mut seen: Vec<usize> = free_list.iter().map(|(_, v)| *v).collect();
+    seen.sort_unstable();
+    assert_eq!(seen, expected);
 }